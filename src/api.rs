@@ -1,14 +1,20 @@
 use anyhow::{anyhow, Result};
 use indexmap::IndexMap;
+use reqwest::Client;
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 
+use crate::cache::DictCache;
+
+#[derive(Serialize)]
 pub struct MoedictItemResult {
     pub pinyin: Option<String>,
     pub bopomofo: Option<String>,
     pub defination: Option<IndexMap<String, Vec<Vec<String>>>>,
 }
 
+#[derive(Serialize)]
 pub struct MoedictResult {
     pub moedict_item_result: Vec<MoedictItemResult>,
     pub translation: Option<IndexMap<String, Vec<String>>>,
@@ -53,9 +59,13 @@ fn format_result(json: HashMap<String, Value>) -> MoedictResult {
     result
 }
 
-fn request_moedict(keyword: &str) -> Result<String> {
-    let response =
-        reqwest::blocking::get(format!("https://www.moedict.tw/a/{}.json", keyword))?.text()?;
+async fn request_moedict(client: &Client, keyword: &str) -> Result<String> {
+    let response = client
+        .get(format!("https://www.moedict.tw/a/{}.json", keyword))
+        .send()
+        .await?
+        .text()
+        .await?;
     let result = response.replace("~", "").replace("`", "");
     if result.contains("<title>404 Not Found</title>") {
         return Err(anyhow!("Could not find keyword: {}", keyword));
@@ -177,8 +187,38 @@ fn get_bopomofo(dict_val: &Value) -> Result<String> {
     Ok(bopomofo)
 }
 
-pub fn get_result(keyword: &str) -> Result<MoedictResult> {
-    let resp = request_moedict(keyword)?;
+/// Parses a raw moedict response already sitting in the cache into a
+/// `MoedictResult`, without touching the network. Used by the
+/// reverse-lookup index to reuse cached entries.
+pub fn parse_entry(raw: &str) -> Result<MoedictResult> {
+    let json: HashMap<String, Value> = serde_json::from_str(raw)?;
+
+    Ok(format_result(json))
+}
+
+/// Looks up `keyword`, consulting the local cache first and only falling
+/// back to the network when the entry is missing. In `offline` mode the
+/// network is never touched, so a cache miss is surfaced as an error
+/// instead.
+pub async fn get_result(
+    keyword: &str,
+    client: &Client,
+    cache: &DictCache,
+    offline: bool,
+) -> Result<MoedictResult> {
+    if let Some(cached) = cache.get(keyword) {
+        let json: HashMap<String, Value> = serde_json::from_str(&cached)?;
+
+        return Ok(format_result(json));
+    }
+    if offline {
+        return Err(anyhow!(
+            "Could not find keyword in offline cache: {}",
+            keyword
+        ));
+    }
+    let resp = request_moedict(client, keyword).await?;
+    cache.put(keyword, &resp)?;
     let json: HashMap<String, Value> = serde_json::from_str(&resp)?;
     let result = format_result(json);
 
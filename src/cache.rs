@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Persistent on-disk cache of moedict lookups, keyed by keyword.
+///
+/// Backed by `sled` so the store can be read and written without running
+/// a separate database process, and so it can double as the embedded
+/// offline dictionary loaded via [`DictCache::import_dump`].
+#[derive(Clone)]
+pub struct DictCache {
+    db: sled::Db,
+}
+
+impl DictCache {
+    /// Opens (creating if necessary) the cache database under the user's
+    /// cache directory, e.g. `~/.cache/meowdict/dict.sled`.
+    pub fn open() -> Result<Self> {
+        let dir = cache_dir()?;
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+        let db = sled::open(dir.join("dict.sled")).context("Failed to open offline cache")?;
+
+        Ok(Self { db })
+    }
+
+    /// Looks up a previously cached (or imported) response for `keyword`.
+    pub fn get(&self, keyword: &str) -> Option<String> {
+        self.db
+            .get(keyword)
+            .ok()
+            .flatten()
+            .and_then(|v| String::from_utf8(v.to_vec()).ok())
+    }
+
+    /// Writes a raw moedict JSON response into the cache.
+    pub fn put(&self, keyword: &str, json: &str) -> Result<()> {
+        self.db.insert(keyword, json.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Returns every headword currently stored in the cache, used to build
+    /// the suggestion index and the reverse-lookup index.
+    pub fn keywords(&self) -> Vec<String> {
+        self.db
+            .iter()
+            .keys()
+            .filter_map(|k| k.ok())
+            .filter_map(|k| String::from_utf8(k.to_vec()).ok())
+            .collect()
+    }
+
+    /// Bulk-loads a dump of moedict JSON (a `{keyword: entry}` object) into
+    /// the cache so meowdict can be used entirely offline. Returns the
+    /// number of entries imported.
+    pub fn import_dump(&self, path: &str) -> Result<usize> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read dump file: {}", path))?;
+        let entries: HashMap<String, Value> =
+            serde_json::from_str(&data).context("Dump is not a valid moedict JSON object")?;
+        for (keyword, entry) in &entries {
+            self.put(keyword, &entry.to_string())?;
+        }
+        self.db.flush().context("Failed to flush offline cache")?;
+
+        Ok(entries.len())
+    }
+}
+
+fn cache_dir() -> Result<PathBuf> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("meowdict"))
+        .ok_or_else(|| anyhow!("Could not determine the user's cache directory"))
+}
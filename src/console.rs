@@ -1,8 +1,11 @@
 use anyhow::{anyhow, Result};
 use reqwest::Client;
 use rustyline::Editor;
+use std::io::BufRead;
+use std::path::PathBuf;
 use tokio::runtime::Runtime;
 
+use crate::cache::DictCache;
 use crate::feat::*;
 use crate::formatter::{opencc_convert, OpenccConvertMode};
 
@@ -12,22 +15,57 @@ pub struct MeowdictConsole {
     pub client: Client,
     pub runtime: Runtime,
     pub no_color_output: bool,
+    pub cache: DictCache,
 }
 
 impl MeowdictConsole {
     pub fn create_console(&mut self) {
+        if atty::is(atty::Stream::Stdin) {
+            self.run_interactive();
+        } else {
+            self.run_piped();
+        }
+    }
+
+    /// Reads one query per line from a piped stdin and exits at EOF,
+    /// e.g. `echo "-j 你好" | meowdict`.
+    fn run_piped(&mut self) {
+        for line in std::io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            self.run_line(&line);
+        }
+    }
+
+    /// Interactive rustyline prompt, with history persisted to
+    /// `~/.cache/meowdict/history` across runs.
+    fn run_interactive(&mut self) {
         let mut reader = Editor::<()>::new();
+        let history_path = history_file();
+        if let Some(path) = &history_path {
+            let _ = reader.load_history(path);
+        }
         while let Ok(argument) = reader.readline("meowdict > ") {
-            let argument = argument
-                .trim()
-                .split(' ')
-                .filter(|x| x != &"")
-                .collect::<Vec<&str>>();
-            if !argument.is_empty() {
-                let (args, words) = argument_spliter(argument);
-                if let Err(e) = self.args_runner(args, words) {
-                    println!("{}", e);
-                }
+            reader.add_history_entry(argument.as_str());
+            self.run_line(&argument);
+        }
+        if let Some(path) = &history_path {
+            let _ = reader.save_history(path);
+        }
+    }
+
+    fn run_line(&mut self, line: &str) {
+        let argument = line
+            .trim()
+            .split(' ')
+            .filter(|x| x != &"")
+            .collect::<Vec<&str>>();
+        if !argument.is_empty() {
+            let (args, words) = argument_spliter(argument);
+            if let Err(e) = self.args_runner(args, words) {
+                println!("{}", e);
             }
         }
     }
@@ -57,6 +95,10 @@ impl MeowdictConsole {
         let mut command_input_s2t = false;
         let mut translation_mode = false;
         let mut jyutping_mode = false;
+        let mut offline_mode = false;
+        let mut import_mode = false;
+        let mut json_mode = false;
+        let mut reverse_mode = false;
         for i in args {
             match i {
                 "--input-s2t" => command_input_s2t = true,
@@ -67,6 +109,11 @@ impl MeowdictConsole {
                 "-t" => translation_mode = true,
                 "--jyutping" => jyutping_mode = true,
                 "-j" => jyutping_mode = true,
+                "--offline" => offline_mode = true,
+                "--import" => import_mode = true,
+                "--json" => json_mode = true,
+                "--reverse" => reverse_mode = true,
+                "-R" => reverse_mode = true,
                 "--set-mode-input-s2t" => self.set_console_mode(&OpenccConvertMode::S2T, true),
                 "--set-mode-result-t2s" => self.set_console_mode(&OpenccConvertMode::T2S, true),
                 "--unset-mode-input-s2t" => self.set_console_mode(&OpenccConvertMode::S2T, false),
@@ -78,19 +125,31 @@ impl MeowdictConsole {
                 _ => return Err(anyhow!("Invaild argument: {}", i)),
             };
         }
+        if import_mode {
+            return self.import_dump(&words_mut);
+        }
         if self.input_s2t || command_input_s2t {
             words_mut = words_mut
                 .into_iter()
                 .map(|x| opencc_convert(&x, OpenccConvertMode::S2T))
                 .collect::<Vec<_>>();
         }
-        if translation_mode {
+        if reverse_mode {
+            if let Err(e) =
+                search_word_to_reverse_result(words_mut, &self.cache, self.no_color_output)
+            {
+                println!("{}", e);
+            }
+        } else if translation_mode {
             if let Err(e) = search_word_to_translation_result(
                 words_mut,
                 &self.client,
                 &self.runtime,
                 self.no_color_output,
                 command_result_t2s || self.result_t2s,
+                &self.cache,
+                offline_mode,
+                json_mode,
             ) {
                 println!("{}", e);
             }
@@ -101,6 +160,9 @@ impl MeowdictConsole {
                 &self.runtime,
                 self.no_color_output,
                 command_result_t2s || self.result_t2s,
+                &self.cache,
+                offline_mode,
+                json_mode,
             ) {
                 println!("{}", e);
             }
@@ -110,12 +172,27 @@ impl MeowdictConsole {
             &self.runtime,
             self.no_color_output,
             command_result_t2s || self.result_t2s,
+            &self.cache,
+            offline_mode,
+            json_mode,
         ) {
             println!("{}", e);
         }
 
         Ok(())
     }
+
+    /// Bulk-loads a dump of moedict JSON into the local offline store,
+    /// e.g. `meowdict --import dump.json`.
+    fn import_dump(&self, words: &[String]) -> Result<()> {
+        let path = words
+            .first()
+            .ok_or_else(|| anyhow!("--import requires a path to a dump file"))?;
+        let count = self.cache.import_dump(path)?;
+        println!("Imported {} entries into the offline cache", count);
+
+        Ok(())
+    }
 }
 
 fn argument_spliter(argument: Vec<&str>) -> (Vec<&str>, Vec<&str>) {
@@ -131,3 +208,12 @@ fn argument_spliter(argument: Vec<&str>) -> (Vec<&str>, Vec<&str>) {
 
     (args, words)
 }
+
+/// Path to the persistent REPL history file, creating its parent
+/// directory if necessary.
+fn history_file() -> Option<PathBuf> {
+    let dir = dirs::cache_dir()?.join("meowdict");
+    std::fs::create_dir_all(&dir).ok()?;
+
+    Some(dir.join("history"))
+}
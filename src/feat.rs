@@ -0,0 +1,283 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use reqwest::Client;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use tokio::sync::Semaphore;
+
+use crate::api::{get_result, MoedictItemResult, MoedictResult};
+use crate::cache::DictCache;
+use crate::formatter::{opencc_convert, OpenccConvertMode};
+use crate::reverse::ReverseIndex;
+use crate::suggest::suggest;
+
+/// Upper bound on in-flight moedict requests for a single batch lookup,
+/// so a large word list doesn't open unbounded concurrent connections.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+pub fn search_word_to_dict_result(
+    words: Vec<String>,
+    client: &Client,
+    runtime: &Runtime,
+    no_color_output: bool,
+    result_t2s: bool,
+    cache: &DictCache,
+    offline: bool,
+    json_mode: bool,
+) -> Result<()> {
+    for (word, result) in fetch_all(words, client, cache, offline, runtime) {
+        match result {
+            Ok(result) if json_mode => print_json_result(result, result_t2s),
+            Ok(result) => print_dict_result(&word, result, no_color_output, result_t2s),
+            Err(e) => print_lookup_error(&word, e, cache),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn search_word_to_translation_result(
+    words: Vec<String>,
+    client: &Client,
+    runtime: &Runtime,
+    no_color_output: bool,
+    result_t2s: bool,
+    cache: &DictCache,
+    offline: bool,
+    json_mode: bool,
+) -> Result<()> {
+    for (word, result) in fetch_all(words, client, cache, offline, runtime) {
+        match result {
+            Ok(result) if json_mode => print_json_result(result, result_t2s),
+            Ok(result) => print_translation_result(&word, result, no_color_output, result_t2s),
+            Err(e) => print_lookup_error(&word, e, cache),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn search_word_to_jyutping_result(
+    words: Vec<String>,
+    client: &Client,
+    runtime: &Runtime,
+    no_color_output: bool,
+    result_t2s: bool,
+    cache: &DictCache,
+    offline: bool,
+    json_mode: bool,
+) -> Result<()> {
+    for (word, result) in fetch_all(words, client, cache, offline, runtime) {
+        match result {
+            Ok(result) if json_mode => print_json_result(result, result_t2s),
+            Ok(result) => print_jyutping_result(&word, result, no_color_output, result_t2s),
+            Err(e) => print_lookup_error(&word, e, cache),
+        }
+    }
+
+    Ok(())
+}
+
+/// Looks up headwords by translation/definition text instead of the
+/// usual headword-to-definition direction, using an index built from
+/// the offline cache.
+pub fn search_word_to_reverse_result(
+    words: Vec<String>,
+    cache: &DictCache,
+    no_color_output: bool,
+) -> Result<()> {
+    let index = ReverseIndex::build(cache);
+    for word in words {
+        let headwords = index.lookup(&word);
+        if headwords.is_empty() {
+            println!("No headwords found for: {}", word);
+        } else {
+            println!(
+                "{}: {}",
+                color_or_plain(&word, no_color_output),
+                headwords.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves every keyword in `words` concurrently (bounded by a
+/// semaphore) on the shared runtime, returning results in the same order
+/// as the input so callers don't need to re-sort.
+fn fetch_all(
+    words: Vec<String>,
+    client: &Client,
+    cache: &DictCache,
+    offline: bool,
+    runtime: &Runtime,
+) -> Vec<(String, Result<MoedictResult>)> {
+    runtime.block_on(async {
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+        let handles: Vec<_> = words
+            .iter()
+            .cloned()
+            .map(|word| {
+                let client = client.clone();
+                let cache = cache.clone();
+                let semaphore = Arc::clone(&semaphore);
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    get_result(&word, &client, &cache, offline).await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (word, handle) in words.into_iter().zip(handles) {
+            let result = match handle.await {
+                Ok(result) => result,
+                Err(e) => Err(anyhow!("Lookup task for \"{}\" failed: {}", word, e)),
+            };
+            results.push((word, result));
+        }
+
+        results
+    })
+}
+
+/// Prints a lookup failure, followed by "Did you mean" suggestions drawn
+/// from the known headwords in the local cache, if any are close enough.
+fn print_lookup_error(word: &str, error: anyhow::Error, cache: &DictCache) {
+    println!("{}", error);
+    let suggestions = suggest(word, &cache.keywords());
+    if !suggestions.is_empty() {
+        println!("Did you mean: {}", suggestions.join(", "));
+    }
+}
+
+/// Prints the assembled result as pretty JSON instead of the formatted
+/// console output, honoring the same `result-t2s` conversion as the
+/// human-readable path.
+fn print_json_result(result: MoedictResult, result_t2s: bool) {
+    let result = if result_t2s {
+        convert_result_t2s(result)
+    } else {
+        result
+    };
+    match serde_json::to_string_pretty(&result) {
+        Ok(json) => println!("{}", json),
+        Err(e) => println!("Failed to serialize result: {}", e),
+    }
+}
+
+fn convert_result_t2s(result: MoedictResult) -> MoedictResult {
+    let moedict_item_result = result
+        .moedict_item_result
+        .into_iter()
+        .map(|item| MoedictItemResult {
+            pinyin: item.pinyin.map(|p| maybe_t2s(&p, true)),
+            bopomofo: item.bopomofo.map(|b| maybe_t2s(&b, true)),
+            defination: item.defination.map(|defination| {
+                defination
+                    .into_iter()
+                    .map(|(kind, entries)| {
+                        let entries = entries
+                            .into_iter()
+                            .map(|entry| entry.iter().map(|line| maybe_t2s(line, true)).collect())
+                            .collect();
+                        (kind, entries)
+                    })
+                    .collect()
+            }),
+        })
+        .collect();
+    let translation = result.translation.map(|translation| {
+        translation
+            .into_iter()
+            .map(|(lang, entries)| {
+                (
+                    lang,
+                    entries.iter().map(|entry| maybe_t2s(entry, true)).collect(),
+                )
+            })
+            .collect()
+    });
+
+    MoedictResult {
+        moedict_item_result,
+        translation,
+    }
+}
+
+fn maybe_t2s(text: &str, result_t2s: bool) -> String {
+    if result_t2s {
+        opencc_convert(text, OpenccConvertMode::T2S)
+    } else {
+        text.to_string()
+    }
+}
+
+fn print_dict_result(word: &str, result: MoedictResult, no_color_output: bool, result_t2s: bool) {
+    let title = maybe_t2s(word, result_t2s);
+    println!("{}", color_or_plain(&title, no_color_output));
+    for item in &result.moedict_item_result {
+        print_item_result(item, no_color_output, result_t2s);
+    }
+}
+
+fn print_item_result(item: &MoedictItemResult, no_color_output: bool, result_t2s: bool) {
+    if let Some(pinyin) = &item.pinyin {
+        println!("  {}", maybe_t2s(pinyin, result_t2s));
+    }
+    if let Some(defination) = &item.defination {
+        for (kind, entries) in defination {
+            println!("  [{}]", kind);
+            for entry in entries {
+                for line in entry {
+                    println!(
+                        "    {}",
+                        color_or_plain(&maybe_t2s(line, result_t2s), no_color_output)
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn print_translation_result(
+    word: &str,
+    result: MoedictResult,
+    no_color_output: bool,
+    result_t2s: bool,
+) {
+    println!("{}", color_or_plain(word, no_color_output));
+    if let Some(translation) = &result.translation {
+        for (lang, entries) in translation {
+            println!("  {}:", lang);
+            for entry in entries {
+                println!("    {}", maybe_t2s(entry, result_t2s));
+            }
+        }
+    } else {
+        println!("  This item has no translation!");
+    }
+}
+
+fn print_jyutping_result(
+    word: &str,
+    result: MoedictResult,
+    no_color_output: bool,
+    result_t2s: bool,
+) {
+    println!("{}", color_or_plain(word, no_color_output));
+    for item in &result.moedict_item_result {
+        if let Some(bopomofo) = &item.bopomofo {
+            println!("  {}", maybe_t2s(bopomofo, result_t2s));
+        }
+    }
+}
+
+fn color_or_plain(text: &str, no_color_output: bool) -> String {
+    if no_color_output {
+        text.to_string()
+    } else {
+        text.green().to_string()
+    }
+}
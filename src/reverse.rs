@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use crate::api::{parse_entry, MoedictResult};
+use crate::cache::DictCache;
+
+/// Inverted index from a case-folded token to the headwords whose
+/// translation or definition text contains it, built from the offline
+/// cache and used to power `meowdict -R <term>`.
+pub struct ReverseIndex {
+    index: HashMap<String, Vec<String>>,
+}
+
+impl ReverseIndex {
+    /// Builds the index from every entry currently sitting in the cache.
+    pub fn build(cache: &DictCache) -> Self {
+        let mut index: HashMap<String, Vec<String>> = HashMap::new();
+        for keyword in cache.keywords() {
+            let raw = match cache.get(&keyword) {
+                Some(raw) => raw,
+                None => continue,
+            };
+            let result = match parse_entry(&raw) {
+                Ok(result) => result,
+                Err(_) => continue,
+            };
+            for token in result_tokens(&result) {
+                let headwords = index.entry(token).or_insert_with(Vec::new);
+                if !headwords.contains(&keyword) {
+                    headwords.push(keyword.clone());
+                }
+            }
+        }
+
+        Self { index }
+    }
+
+    /// Returns the headwords whose translation or definition contains
+    /// `term`, matching case-insensitively for Latin input. `term` is
+    /// tokenized the same way entries are at build time, so a multi-word
+    /// Latin query (e.g. "go lucky") or a multi-character CJK query
+    /// (e.g. "快樂") is matched by intersecting the headwords for each
+    /// of its tokens.
+    pub fn lookup(&self, term: &str) -> Vec<String> {
+        tokenize(term)
+            .iter()
+            .map(|token| self.index.get(token).cloned().unwrap_or_default())
+            .reduce(|acc, headwords| acc.into_iter().filter(|h| headwords.contains(h)).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn result_tokens(result: &MoedictResult) -> Vec<String> {
+    let mut tokens = Vec::new();
+    if let Some(translation) = &result.translation {
+        for entries in translation.values() {
+            for entry in entries {
+                tokens.extend(tokenize(entry));
+            }
+        }
+    }
+    for item in &result.moedict_item_result {
+        if let Some(defination) = &item.defination {
+            for entries in defination.values() {
+                for entry in entries {
+                    for line in entry {
+                        tokens.extend(tokenize(line));
+                    }
+                }
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Splits free text into searchable tokens: runs of Latin/digit
+/// characters become one case-folded word (so e.g. "Happy-go-lucky"
+/// indexes as ["happy", "go", "lucky"]), while CJK ideographs, which
+/// aren't space-delimited, are each indexed as their own token so a
+/// multi-character Chinese definition like "快樂的樣子" is still found
+/// by a shorter query such as "快樂".
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c.is_lowercase() || c.is_uppercase() {
+            word.extend(c.to_lowercase());
+        } else {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            if c.is_alphanumeric() {
+                tokens.push(c.to_string());
+            }
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+
+    tokens
+}
@@ -0,0 +1,68 @@
+/// Maximum edit distance still considered a useful suggestion.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+/// Maximum number of "Did you mean" candidates to surface.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Bounded Levenshtein edit distance between `query` and `candidate`.
+///
+/// Computed with the standard DP table (rows = query chars, cols =
+/// candidate chars, cost 1 for insert/delete/substitute), but a candidate
+/// is abandoned as soon as the running row minimum exceeds
+/// `max_distance`, so checking against thousands of headwords stays fast.
+/// Returns `None` once the candidate is known to exceed `max_distance`.
+pub fn bounded_edit_distance(query: &str, candidate: &str, max_distance: usize) -> Option<usize> {
+    let query: Vec<char> = query.chars().collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    if query.len().abs_diff(candidate.len()) > max_distance {
+        return None;
+    }
+    let mut prev_row: Vec<usize> = (0..=candidate.len()).collect();
+    for (i, query_char) in query.iter().enumerate() {
+        let mut row = vec![0usize; candidate.len() + 1];
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for (j, candidate_char) in candidate.iter().enumerate() {
+            let cost = if query_char == candidate_char { 0 } else { 1 };
+            row[j + 1] = (prev_row[j + 1] + 1)
+                .min(row[j] + 1)
+                .min(prev_row[j] + cost);
+            row_min = row_min.min(row[j + 1]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        prev_row = row;
+    }
+    let distance = prev_row[candidate.len()];
+
+    if distance <= max_distance {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+/// Returns the closest known headwords to `query`, preferring candidates
+/// that share its first character, for use as "Did you mean" suggestions.
+pub fn suggest(query: &str, headwords: &[String]) -> Vec<String> {
+    let query_first_char = query.chars().next();
+    let mut scored: Vec<(usize, bool, &String)> = headwords
+        .iter()
+        .filter_map(|candidate| {
+            bounded_edit_distance(query, candidate, MAX_SUGGESTION_DISTANCE).map(|distance| {
+                (
+                    distance,
+                    candidate.chars().next() == query_first_char,
+                    candidate,
+                )
+            })
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, candidate)| candidate.clone())
+        .collect()
+}